@@ -0,0 +1,75 @@
+//! Hand-rolled flag parsing. The tool has a handful of switches, not enough
+//! to justify pulling in a dependency like clap.
+
+use std::env;
+
+use crate::level::LevelFilter;
+use crate::output::OutputFormat;
+use crate::template::{self, Token};
+
+pub struct Cli {
+    pub layout: Vec<Token>,
+    pub format: Option<OutputFormat>,
+    pub filter: Option<String>,
+    pub time_format: Option<String>,
+    pub utc: bool,
+    pub level_filter: Option<LevelFilter>,
+}
+
+impl Cli {
+    pub fn parse() -> Self {
+        let mut layout = env::var("LOGFMT_LAYOUT").ok();
+        let mut format = None;
+        let mut filter = None;
+        let mut time_format = None;
+        let mut utc = false;
+        let mut level = None;
+        let mut only = None;
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--layout" => layout = args.next(),
+                "--format" => {
+                    let value = args.next().expect("--format requires a value");
+                    format = Some(
+                        OutputFormat::parse(&value)
+                            .unwrap_or_else(|| panic!("unknown --format: {}", value)),
+                    );
+                }
+                "--filter" => filter = Some(args.next().expect("--filter requires a value")),
+                "--time-format" => {
+                    time_format = Some(args.next().expect("--time-format requires a value"))
+                }
+                "--utc" => utc = true,
+                "--level" => level = Some(args.next().expect("--level requires a value")),
+                "--only" => only = Some(args.next().expect("--only requires a value")),
+                _ => {}
+            }
+        }
+
+        let layout = layout
+            .as_deref()
+            .map(template::parse)
+            .unwrap_or_else(template::default);
+
+        let level_filter = match only {
+            Some(levels) => Some(
+                LevelFilter::only(&levels)
+                    .unwrap_or_else(|| panic!("unknown level in --only: {}", levels)),
+            ),
+            None => level.map(|min| {
+                LevelFilter::threshold(&min).unwrap_or_else(|| panic!("unknown --level: {}", min))
+            }),
+        };
+
+        Cli {
+            layout,
+            format,
+            filter,
+            time_format,
+            utc,
+            level_filter,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Whether to emit ANSI escapes at all, following the de-facto env
+//! conventions documented by yansi's `detect-env` feature: `NO_COLOR` always
+//! wins, `CLICOLOR=0` disables, and `CLICOLOR_FORCE` forces colour on even
+//! when stdout isn't a TTY.
+
+use std::sync::OnceLock;
+
+use ansi_term::Colour;
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+pub fn init(tty: bool) {
+    let enabled = if std::env::var_os("NO_COLOR").is_some() {
+        false
+    } else if std::env::var_os("CLICOLOR_FORCE").is_some() {
+        true
+    } else if std::env::var("CLICOLOR").as_deref() == Ok("0") {
+        false
+    } else {
+        tty
+    };
+
+    let _ = ENABLED.set(enabled);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.get().copied().unwrap_or(false)
+}
+
+pub fn paint(colour: Colour, s: impl Into<String>) -> String {
+    let s = s.into();
+    if enabled() {
+        colour.paint(s).to_string()
+    } else {
+        s
+    }
+}
+
+pub fn paint_bold(colour: Colour, s: impl Into<String>) -> String {
+    let s = s.into();
+    if enabled() {
+        colour.bold().paint(s).to_string()
+    } else {
+        s
+    }
+}
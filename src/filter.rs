@@ -0,0 +1,67 @@
+//! Optional Lua record filter/rewriter, evaluated once per line the way
+//! fblog's `--filter` does. The chunk is compiled once at startup and
+//! reused for every record so the stdin loop doesn't pay parse cost per
+//! line. The script sees the record as a global `record` table and returns
+//! either a boolean (print/drop) or a table (a replacement record).
+
+use ansi_term::Colour;
+use mlua::{Lua, LuaSerdeExt, RegistryKey, Value as LuaValue};
+use serde_json::map::Map;
+use serde_json::Value;
+
+use crate::color;
+
+pub struct Filter {
+    lua: Lua,
+    chunk: RegistryKey,
+}
+
+impl Filter {
+    pub fn compile(chunk: String) -> Self {
+        let lua = Lua::new();
+
+        let function = lua
+            .load(&chunk)
+            .into_function()
+            .expect("invalid --filter script");
+
+        let chunk = lua
+            .create_registry_value(function)
+            .expect("failed to cache compiled --filter script");
+
+        Filter { lua, chunk }
+    }
+
+    /// Returns `None` if the line should be dropped, `Some(record)` (the
+    /// original or a rewritten one) otherwise. On a Lua error, prints a red
+    /// diagnostic to stderr and drops the line rather than aborting.
+    pub fn apply(&self, record: Map<String, Value>) -> Option<Map<String, Value>> {
+        match self.eval(&record) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    color::paint(Colour::Red, format!("lua filter error: {}", e))
+                );
+                None
+            }
+        }
+    }
+
+    fn eval(&self, record: &Map<String, Value>) -> mlua::Result<Option<Map<String, Value>>> {
+        let table = self.lua.to_value(&Value::Object(record.clone()))?;
+        self.lua.globals().set("record", table)?;
+
+        let function: mlua::Function = self.lua.registry_value(&self.chunk)?;
+        let outcome: LuaValue = function.call(())?;
+
+        Ok(match outcome {
+            LuaValue::Boolean(keep) => keep.then(|| record.clone()),
+            LuaValue::Table(_) => match self.lua.from_value::<Value>(outcome)? {
+                Value::Object(rewritten) => Some(rewritten),
+                _ => Some(record.clone()),
+            },
+            _ => Some(record.clone()),
+        })
+    }
+}
@@ -0,0 +1,95 @@
+use ansi_term::Colour;
+
+use crate::color;
+use crate::theme;
+use crate::when;
+
+pub trait Format {
+    fn format<T: Formatter>(&self) -> String;
+}
+
+impl Format for Option<&str> {
+    fn format<T: Formatter>(&self) -> String {
+        T::format(*self)
+    }
+}
+
+pub trait Formatter {
+    fn format(s: Option<&str>) -> String;
+}
+
+pub struct LevelFormatter;
+impl Formatter for LevelFormatter {
+    fn format(level: Option<&str>) -> String {
+        let level = level.or(Some("XXXXXX")).unwrap();
+
+        color::paint(theme::colour_for(level), format!("[{:^7}]", level))
+    }
+}
+
+pub struct WhenFormatter;
+impl Formatter for WhenFormatter {
+    fn format(when: Option<&str>) -> String {
+        when::format(when)
+    }
+}
+
+pub struct PidFormatter;
+impl Formatter for PidFormatter {
+    fn format(pid: Option<&str>) -> String {
+        let pid = pid.or(Some("XXXXXX")).unwrap();
+        color::paint(Colour::Blue, format!("{:<10}", pid))
+    }
+}
+
+pub struct WhatFormatter;
+impl Formatter for WhatFormatter {
+    fn format(what: Option<&str>) -> String {
+        let what = what.or(Some("")).unwrap();
+
+        color::paint(Colour::White, what)
+    }
+}
+
+pub struct InFormatter;
+impl Formatter for InFormatter {
+    fn format(in_: Option<&str>) -> String {
+        let in_ = in_.or(Some("")).unwrap();
+
+        color::paint(Colour::White, format!("| {}", in_))
+    }
+}
+
+pub struct TextFormatter;
+impl Formatter for TextFormatter {
+    fn format(text: Option<&str>) -> String {
+        format!(
+            "{} {}",
+            color::paint_bold(Colour::Blue, ">>"),
+            color::paint(Colour::Green, text.unwrap_or(""))
+        )
+    }
+}
+
+/// Raw passthrough used by the template engine for fields that have no
+/// named formatter (either left unannotated or unknown).
+pub struct PassthroughFormatter;
+impl Formatter for PassthroughFormatter {
+    fn format(s: Option<&str>) -> String {
+        s.unwrap_or("").to_string()
+    }
+}
+
+/// Looks up a formatter by the name used in a `{field:name}` template
+/// placeholder, falling back to a raw passthrough for unknown names.
+pub fn lookup(name: &str) -> fn(Option<&str>) -> String {
+    match name {
+        "time" => WhenFormatter::format,
+        "level" => LevelFormatter::format,
+        "pid" => PidFormatter::format,
+        "what" => WhatFormatter::format,
+        "in" => InFormatter::format,
+        "text" => TextFormatter::format,
+        _ => PassthroughFormatter::format,
+    }
+}
@@ -0,0 +1,56 @@
+//! Severity ordering for the `level` field, reusing the ranking already
+//! implied by `LevelFormatter`'s palette: `debug < info < notice < warning
+//! < error < critical < alert`. Used to pre-filter the stdin loop before a
+//! record is ever formatted.
+
+const ORDER: [&str; 7] = [
+    "debug", "info", "notice", "warning", "error", "critical", "alert",
+];
+
+fn rank(level: &str) -> Option<usize> {
+    ORDER.iter().position(|&l| l == level)
+}
+
+pub enum LevelFilter {
+    Threshold(usize),
+    Only(Vec<String>),
+}
+
+impl LevelFilter {
+    pub fn threshold(min: &str) -> Option<Self> {
+        rank(min).map(LevelFilter::Threshold)
+    }
+
+    /// Returns `None` if any entry in the comma-separated list isn't a
+    /// recognised level, mirroring `threshold`'s validation.
+    pub fn only(levels: &str) -> Option<Self> {
+        let levels: Vec<&str> = levels.split(',').map(str::trim).collect();
+
+        if levels.iter().any(|l| rank(l).is_none()) {
+            return None;
+        }
+
+        Some(LevelFilter::Only(
+            levels.into_iter().map(str::to_string).collect(),
+        ))
+    }
+
+    /// Records with a missing or unrecognised level are always shown so
+    /// nothing is silently lost.
+    pub fn allows(&self, level: Option<&str>) -> bool {
+        let level = match level {
+            Some(level) => level,
+            None => return true,
+        };
+
+        let rank = match rank(level) {
+            Some(rank) => rank,
+            None => return true,
+        };
+
+        match self {
+            LevelFilter::Only(levels) => levels.iter().any(|l| l == level),
+            LevelFilter::Threshold(min) => rank >= *min,
+        }
+    }
+}
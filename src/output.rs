@@ -0,0 +1,110 @@
+//! Runtime-selectable output modes, mirroring the way libtest exposes its
+//! `pretty`, `terse`, and `json` test formatters behind one flag. Each mode
+//! implements `RecordWriter`; `main` only has to pick the right one and stay
+//! out of the formatting business.
+
+use serde_json::map::Map;
+use serde_json::Value;
+
+use colored_json::{to_colored_json, ColorMode};
+
+use crate::color;
+use crate::formatters::{self, Format};
+use crate::template::{self, Token};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Pretty,
+    Compact,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pretty" => Some(OutputFormat::Pretty),
+            "compact" => Some(OutputFormat::Compact),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+pub trait RecordWriter {
+    /// `raw` is the exact input line, used by formats (like json) that must
+    /// re-emit the record untouched rather than rebuild it from `record`.
+    /// When a `--filter` script ran, `raw` is instead the record it produced,
+    /// re-serialized.
+    fn write(&self, record: Map<String, Value>, raw: &str);
+}
+
+const RESERVED_KEYS: [&str; 9] = [
+    "when", "level", "pid", "what", "in", "at", "log", "id", "text",
+];
+
+pub struct PrettyFormatter<'a> {
+    pub layout: &'a [Token],
+}
+
+impl<'a> RecordWriter for PrettyFormatter<'a> {
+    fn write(&self, mut line: Map<String, Value>, _raw: &str) {
+        println!("{}", template::render(self.layout, &line));
+
+        let text = line.get("text").and_then(Value::as_str);
+        if text.is_some() {
+            println!("{}", text.format::<formatters::TextFormatter>());
+        }
+
+        for k in RESERVED_KEYS {
+            line.remove(k);
+        }
+
+        if !line.is_empty() {
+            let mode = if color::enabled() {
+                ColorMode::On
+            } else {
+                ColorMode::Off
+            };
+            let highlighted = to_colored_json(&Value::Object(line), mode);
+            println!("{}\n", highlighted.unwrap());
+        }
+    }
+}
+
+pub struct CompactFormatter<'a> {
+    pub layout: &'a [Token],
+}
+
+impl<'a> RecordWriter for CompactFormatter<'a> {
+    fn write(&self, mut line: Map<String, Value>, _raw: &str) {
+        let mut out = template::render(self.layout, &line);
+
+        let text = line.get("text").and_then(Value::as_str).map(String::from);
+
+        for k in RESERVED_KEYS {
+            line.remove(k);
+        }
+
+        if let Some(text) = text {
+            out.push_str(&format!(" text={}", text));
+        }
+
+        for (k, v) in &line {
+            let v = match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            out.push_str(&format!(" {}={}", k, v));
+        }
+
+        println!("{}", out);
+    }
+}
+
+pub struct JsonFormatter;
+
+impl RecordWriter for JsonFormatter {
+    fn write(&self, _record: Map<String, Value>, raw: &str) {
+        println!("{}", raw);
+    }
+}
@@ -0,0 +1,93 @@
+//! Parses a layout string such as `"{when:time} {level:level} {pid:pid} |
+//! {in:in} {what:what}"` into a sequence of tokens that `render` replays
+//! at print time, the same way fblog drives its `main_line`
+//! handlebars template. Each `{field}` placeholder may be annotated with a
+//! formatter name (`{when:time}`); an unannotated or unknown name falls
+//! back to a raw passthrough via `formatters::lookup`.
+
+use serde_json::map::Map;
+use serde_json::Value;
+
+use crate::formatters;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Literal(String),
+    Field {
+        name: String,
+        formatter: Option<String>,
+    },
+}
+
+pub fn parse(template: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            literal.push(c);
+            continue;
+        }
+
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(std::mem::take(&mut literal)));
+        }
+
+        let mut field = String::new();
+        for c in chars.by_ref() {
+            if c == '}' {
+                break;
+            }
+            field.push(c);
+        }
+
+        let (name, formatter) = match field.split_once(':') {
+            Some((name, formatter)) => (name.to_string(), Some(formatter.to_string())),
+            None => (field, None),
+        };
+
+        tokens.push(Token::Field { name, formatter });
+    }
+
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+
+    tokens
+}
+
+/// The layout used when no `--layout` flag or `LOGFMT_LAYOUT` env var is
+/// given, matching the previous hard-coded `when level pid in what` order.
+pub fn default() -> Vec<Token> {
+    parse("{when:time} {level:level} {pid:pid} {in:in} {what:what}")
+}
+
+pub fn render(tokens: &[Token], record: &Map<String, Value>) -> String {
+    let mut out = String::new();
+
+    for token in tokens {
+        match token {
+            Token::Literal(s) => out.push_str(s),
+            Token::Field { name, formatter } => {
+                let owned;
+                let value = match record.get(name) {
+                    Some(Value::String(s)) => Some(s.as_str()),
+                    Some(Value::Number(n)) => {
+                        owned = n.to_string();
+                        Some(owned.as_str())
+                    }
+                    _ => None,
+                };
+
+                let format: fn(Option<&str>) -> String = match formatter {
+                    Some(formatter) => formatters::lookup(formatter),
+                    None => <formatters::PassthroughFormatter as formatters::Formatter>::format,
+                };
+                out.push_str(&format(value));
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,92 @@
+//! Data-driven palette mapping level names to `ansi_term::Colour`, loaded
+//! from the `LOGFMT_THEME` env var or a `theme.toml` file and falling back
+//! to the built-in palette. This lets users adapt to light/dark terminals
+//! and assign colours to custom levels that would otherwise fall through to
+//! the default.
+
+use std::collections::HashMap;
+use std::fs;
+use std::sync::OnceLock;
+
+use ansi_term::Colour;
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+struct Theme {
+    levels: HashMap<String, Colour>,
+    default: Colour,
+}
+
+pub fn init() {
+    let _ = THEME.set(Theme::load());
+}
+
+pub fn colour_for(level: &str) -> Colour {
+    THEME.get_or_init(Theme::load).colour(level)
+}
+
+impl Theme {
+    fn load() -> Self {
+        let mut levels = builtin();
+
+        let overrides = std::env::var("LOGFMT_THEME")
+            .ok()
+            .or_else(|| fs::read_to_string("theme.toml").ok());
+
+        if let Some(source) = overrides {
+            levels.extend(parse(&source));
+        }
+
+        Theme {
+            levels,
+            default: Colour::Blue,
+        }
+    }
+
+    fn colour(&self, level: &str) -> Colour {
+        self.levels.get(level).copied().unwrap_or(self.default)
+    }
+}
+
+fn builtin() -> HashMap<String, Colour> {
+    [
+        ("alert", Colour::Red),
+        ("critical", Colour::Red),
+        ("error", Colour::Red),
+        ("warning", Colour::Yellow),
+        ("notice", Colour::Yellow),
+        ("info", Colour::Blue),
+        ("debug", Colour::Purple),
+    ]
+    .into_iter()
+    .map(|(level, colour)| (level.to_string(), colour))
+    .collect()
+}
+
+/// Parses `level = colour` pairs separated by commas or newlines — a small
+/// hand-rolled reader, not a full TOML parser, but `theme.toml` files using
+/// just that one line shape parse fine either way.
+fn parse(source: &str) -> HashMap<String, Colour> {
+    source
+        .split(['\n', ','])
+        .filter_map(|entry| entry.split_once('='))
+        .filter_map(|(level, colour)| {
+            let colour = colour_by_name(colour.trim().trim_matches('"'))?;
+            Some((level.trim().to_string(), colour))
+        })
+        .collect()
+}
+
+fn colour_by_name(name: &str) -> Option<Colour> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "black" => Colour::Black,
+        "red" => Colour::Red,
+        "green" => Colour::Green,
+        "yellow" => Colour::Yellow,
+        "blue" => Colour::Blue,
+        "purple" => Colour::Purple,
+        "cyan" => Colour::Cyan,
+        "white" => Colour::White,
+        _ => return None,
+    })
+}
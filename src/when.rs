@@ -0,0 +1,66 @@
+//! Configurable parsing/formatting for the `when` field, replacing the
+//! previous hard-coded `DateTime<Local>` + `%Y-%m-%d %H:%M:%S` pair with a
+//! `--time-format`/`--utc` pair set once at startup, the way
+//! flexi_logger/simplelog expose `TimeFormat`/`UtcOffset`. Also accepts
+//! epoch-millis/epoch-seconds values, common in JSON logs, and falls back
+//! to echoing the raw string instead of panicking when nothing parses.
+
+use std::sync::OnceLock;
+
+use ansi_term::Colour;
+use chrono::{DateTime, Local, TimeZone, Utc};
+
+use crate::color;
+
+const DEFAULT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+struct Config {
+    format: String,
+    utc: bool,
+}
+
+pub fn init(format: Option<String>, utc: bool) {
+    let _ = CONFIG.set(Config {
+        format: format.unwrap_or_else(|| DEFAULT_FORMAT.to_string()),
+        utc,
+    });
+}
+
+fn config() -> &'static Config {
+    CONFIG.get_or_init(|| Config {
+        format: DEFAULT_FORMAT.to_string(),
+        utc: false,
+    })
+}
+
+pub fn format(when: Option<&str>) -> String {
+    let rendered = match when {
+        None => "XXXX-XX-XX XX:XX:XX".to_string(),
+        Some(when) => render(when).unwrap_or_else(|| when.to_string()),
+    };
+
+    color::paint(Colour::Blue, rendered)
+}
+
+fn render(when: &str) -> Option<String> {
+    let config = config();
+
+    let utc = match when.parse::<i64>() {
+        Ok(n) if when.trim_start_matches('-').len() > 10 => Utc.timestamp_millis_opt(n).single(),
+        Ok(n) => Utc.timestamp_opt(n, 0).single(),
+        Err(_) => None,
+    };
+
+    let utc = match utc {
+        Some(utc) => Some(utc),
+        None => when.parse::<DateTime<Utc>>().ok(),
+    }?;
+
+    Some(if config.utc {
+        utc.format(&config.format).to_string()
+    } else {
+        utc.with_timezone(&Local).format(&config.format).to_string()
+    })
+}